@@ -1,11 +1,160 @@
 use anyhow::{Context, Result};
+use chrono::{DateTime, FixedOffset, NaiveDate, Utc};
+use flate2::read::GzDecoder;
+use futures::future::{BoxFuture, FutureExt};
 use indicatif::{ProgressBar, ProgressStyle};
 use quick_xml::events::Event;
 use quick_xml::reader::Reader;
-use std::collections::HashMap;
-use std::io::{self, Write};
-use std::time::Duration;
+use std::collections::{HashMap, HashSet};
 use std::env;
+use std::io::{self, Read};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{Mutex, Semaphore};
+use tokio::time::Instant;
+
+const DEFAULT_CONCURRENCY: usize = 16;
+const DEFAULT_MAX_DEPTH: usize = 10;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum OutputFormat {
+    Text,
+    Json,
+    Csv,
+}
+
+impl OutputFormat {
+    fn parse(text: &str) -> Option<Self> {
+        match text {
+            "text" => Some(Self::Text),
+            "json" => Some(Self::Json),
+            "csv" => Some(Self::Csv),
+            _ => None,
+        }
+    }
+
+    fn is_machine(self) -> bool {
+        self != Self::Text
+    }
+}
+
+struct Args {
+    main_sitemap_url: String,
+    debug: bool,
+    concurrency: usize,
+    rate_limit: Option<f64>,
+    max_depth: usize,
+    check_links: bool,
+    output: OutputFormat,
+}
+
+// Routes to stderr instead of stdout when a machine `--output` format is
+// selected, so stdout stays reserved for the JSON/CSV payload.
+macro_rules! chatter {
+    ($machine:expr, $($arg:tt)*) => {
+        if $machine {
+            eprintln!($($arg)*);
+        } else {
+            println!($($arg)*);
+        }
+    };
+}
+
+fn parse_args() -> Result<Args> {
+    let raw: Vec<String> = env::args().collect();
+    let mut main_sitemap_url = None;
+    let mut debug = false;
+    let mut concurrency = DEFAULT_CONCURRENCY;
+    let mut rate_limit = None;
+    let mut max_depth = DEFAULT_MAX_DEPTH;
+    let mut check_links = false;
+    let mut output = OutputFormat::Text;
+
+    let mut i = 1;
+    while i < raw.len() {
+        match raw[i].as_str() {
+            "--debug" => debug = true,
+            "--check-links" => check_links = true,
+            "--concurrency" => {
+                i += 1;
+                let value = raw
+                    .get(i)
+                    .context("--concurrency requires a value")?;
+                concurrency = value
+                    .parse()
+                    .context("--concurrency must be a positive integer")?;
+                if concurrency < 1 {
+                    return Err(anyhow::anyhow!("--concurrency must be at least 1"));
+                }
+            }
+            "--rate-limit" => {
+                i += 1;
+                let value = raw.get(i).context("--rate-limit requires a value")?;
+                let value: f64 = value.parse().context("--rate-limit must be a number")?;
+                if value <= 0.0 {
+                    return Err(anyhow::anyhow!("--rate-limit must be greater than 0"));
+                }
+                rate_limit = Some(value);
+            }
+            "--max-depth" => {
+                i += 1;
+                let value = raw.get(i).context("--max-depth requires a value")?;
+                max_depth = value
+                    .parse()
+                    .context("--max-depth must be a non-negative integer")?;
+            }
+            "--output" => {
+                i += 1;
+                let value = raw.get(i).context("--output requires a value")?;
+                output = OutputFormat::parse(value)
+                    .with_context(|| format!("--output must be one of text, json, csv (got {})", value))?;
+            }
+            other if main_sitemap_url.is_none() => main_sitemap_url = Some(other.to_string()),
+            other => return Err(anyhow::anyhow!("Unrecognized argument: {}", other)),
+        }
+        i += 1;
+    }
+
+    let main_sitemap_url = main_sitemap_url.context(format!(
+        "Usage: {} <sitemap_url> [--debug] [--concurrency N] [--rate-limit R] [--max-depth N] [--check-links] [--output {{text,json,csv}}]",
+        raw[0]
+    ))?;
+
+    Ok(Args {
+        main_sitemap_url,
+        debug,
+        concurrency,
+        rate_limit,
+        max_depth,
+        check_links,
+        output,
+    })
+}
+
+// Token-bucket limiter shared across tasks so a target host isn't hammered.
+struct RateLimiter {
+    interval: Duration,
+    next_slot: Mutex<Instant>,
+}
+
+impl RateLimiter {
+    fn new(requests_per_second: f64) -> Self {
+        Self {
+            interval: Duration::from_secs_f64(1.0 / requests_per_second),
+            next_slot: Mutex::new(Instant::now()),
+        }
+    }
+
+    async fn acquire(&self) {
+        let mut next_slot = self.next_slot.lock().await;
+        let now = Instant::now();
+        let wait_until = if *next_slot > now { *next_slot } else { now };
+        *next_slot = wait_until + self.interval;
+        drop(next_slot);
+        tokio::time::sleep_until(wait_until).await;
+    }
+}
 
 fn clean_xml_content(content: &str) -> String {
     content
@@ -17,115 +166,568 @@ fn clean_xml_content(content: &str) -> String {
         .to_string()
 }
 
-fn main() -> Result<()> {
-    let args: Vec<String> = env::args().collect();
-    if args.len() < 2 || args.len() > 3 {
-        println!("Usage: {} <sitemap_url> [--debug]", args[0]);
-        std::process::exit(1);
+enum RootKind {
+    SitemapIndex,
+    UrlSet,
+}
+
+fn detect_root(content: &str) -> Result<RootKind> {
+    let mut reader = Reader::from_str(content);
+    reader.trim_text(true);
+    let mut buf = Vec::new();
+    loop {
+        match reader.read_event_into(&mut buf)? {
+            Event::Start(ref e) | Event::Empty(ref e) => {
+                return match e.local_name().as_ref() {
+                    b"sitemapindex" => Ok(RootKind::SitemapIndex),
+                    b"urlset" => Ok(RootKind::UrlSet),
+                    other => Err(anyhow::anyhow!(
+                        "Unrecognized root element <{}>; expected <sitemapindex> or <urlset>",
+                        String::from_utf8_lossy(other)
+                    )),
+                };
+            }
+            Event::Eof => return Err(anyhow::anyhow!("Empty or invalid sitemap XML")),
+            _ => {}
+        }
+        buf.clear();
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum ChangeFreq {
+    Always,
+    Hourly,
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+    Never,
+}
+
+impl ChangeFreq {
+    const ALL: [ChangeFreq; 7] = [
+        ChangeFreq::Always,
+        ChangeFreq::Hourly,
+        ChangeFreq::Daily,
+        ChangeFreq::Weekly,
+        ChangeFreq::Monthly,
+        ChangeFreq::Yearly,
+        ChangeFreq::Never,
+    ];
+
+    fn parse(text: &str) -> Option<Self> {
+        match text.trim().to_ascii_lowercase().as_str() {
+            "always" => Some(Self::Always),
+            "hourly" => Some(Self::Hourly),
+            "daily" => Some(Self::Daily),
+            "weekly" => Some(Self::Weekly),
+            "monthly" => Some(Self::Monthly),
+            "yearly" => Some(Self::Yearly),
+            "never" => Some(Self::Never),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for ChangeFreq {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Self::Always => "always",
+            Self::Hourly => "hourly",
+            Self::Daily => "daily",
+            Self::Weekly => "weekly",
+            Self::Monthly => "monthly",
+            Self::Yearly => "yearly",
+            Self::Never => "never",
+        };
+        f.write_str(s)
+    }
+}
+
+#[derive(Debug, Clone)]
+struct UrlEntry {
+    loc: String,
+    lastmod: Option<DateTime<FixedOffset>>,
+    changefreq: Option<ChangeFreq>,
+    priority: Option<f32>,
+}
+
+// Accepts either a full W3C datetime or a bare date.
+fn parse_lastmod(text: &str) -> Option<DateTime<FixedOffset>> {
+    DateTime::parse_from_rfc3339(text).ok().or_else(|| {
+        NaiveDate::parse_from_str(text, "%Y-%m-%d")
+            .ok()
+            .and_then(|date| date.and_hms_opt(0, 0, 0))
+            .map(|naive| DateTime::<Utc>::from_naive_utc_and_offset(naive, Utc).fixed_offset())
+    })
+}
+
+fn extract_url_entries(content: &str, debug: bool) -> Result<Vec<UrlEntry>> {
+    let mut reader = Reader::from_str(content);
+    let mut buf = Vec::new();
+    let mut entries = Vec::new();
+
+    let mut in_url = false;
+    let mut current_tag: Option<Vec<u8>> = None;
+    let mut loc = None;
+    let mut lastmod = None;
+    let mut changefreq = None;
+    let mut priority = None;
+
+    loop {
+        match reader.read_event_into(&mut buf) {
+            Ok(Event::Start(ref e)) if e.name().as_ref() == b"url" => {
+                in_url = true;
+                loc = None;
+                lastmod = None;
+                changefreq = None;
+                priority = None;
+            }
+            Ok(Event::End(ref e)) if e.name().as_ref() == b"url" => {
+                in_url = false;
+                if let Some(loc) = loc.take() {
+                    entries.push(UrlEntry {
+                        loc,
+                        lastmod: lastmod.take(),
+                        changefreq: changefreq.take(),
+                        priority: priority.take(),
+                    });
+                }
+            }
+            Ok(Event::Start(ref e)) if in_url => {
+                current_tag = Some(e.name().as_ref().to_vec());
+            }
+            Ok(Event::End(ref e)) if in_url && current_tag.as_deref() == Some(e.name().as_ref()) => {
+                current_tag = None;
+            }
+            Ok(Event::Text(e)) if in_url && current_tag.is_some() => {
+                let text = e.unescape()?.into_owned();
+                match current_tag.as_deref() {
+                    Some(b"loc") => loc = Some(text),
+                    Some(b"lastmod") => lastmod = parse_lastmod(&text),
+                    Some(b"changefreq") => changefreq = ChangeFreq::parse(&text),
+                    Some(b"priority") => {
+                        priority = text.trim().parse().ok().filter(|p: &f32| p.is_finite())
+                    }
+                    _ => {}
+                }
+            }
+            Ok(Event::Eof) => break,
+            Err(e) => return Err(e.into()),
+            _ => (),
+        }
+        buf.clear();
+    }
+    if debug { eprintln!("[DEBUG] Extracted {} URL entries.", entries.len()); }
+    Ok(entries)
+}
+
+fn print_metadata_summary(entries: &[UrlEntry], machine: bool) {
+    let mut by_changefreq: HashMap<ChangeFreq, usize> = HashMap::new();
+    let mut missing_lastmod = 0;
+    let mut missing_changefreq = 0;
+    let mut missing_priority = 0;
+    let mut oldest: Option<DateTime<FixedOffset>> = None;
+    let mut newest: Option<DateTime<FixedOffset>> = None;
+
+    for entry in entries {
+        match entry.changefreq {
+            Some(freq) => *by_changefreq.entry(freq).or_insert(0) += 1,
+            None => missing_changefreq += 1,
+        }
+        if entry.priority.is_none() {
+            missing_priority += 1;
+        }
+        match entry.lastmod {
+            Some(lastmod) => {
+                oldest = Some(oldest.map_or(lastmod, |o| o.min(lastmod)));
+                newest = Some(newest.map_or(lastmod, |n| n.max(lastmod)));
+            }
+            None => missing_lastmod += 1,
+        }
+    }
+
+    chatter!(machine, "\n🗓️  URL metadata:");
+    for freq in ChangeFreq::ALL {
+        if let Some(count) = by_changefreq.get(&freq) {
+            chatter!(machine, "  {} - {} URLs", freq, count);
+        }
+    }
+    if let Some(oldest) = oldest {
+        chatter!(machine, "  oldest lastmod: {}", oldest.to_rfc3339());
+    }
+    if let Some(newest) = newest {
+        chatter!(machine, "  newest lastmod: {}", newest.to_rfc3339());
+    }
+    chatter!(
+        machine,
+        "  missing lastmod: {}, missing changefreq: {}, missing priority: {}",
+        missing_lastmod, missing_changefreq, missing_priority
+    );
+}
+
+#[derive(Clone)]
+struct Crawler {
+    client: reqwest::Client,
+    semaphore: Arc<Semaphore>,
+    rate_limiter: Option<Arc<RateLimiter>>,
+    visited: Arc<Mutex<HashSet<String>>>,
+    max_depth: usize,
+    debug: bool,
+    pb: ProgressBar,
+    completed: Arc<AtomicU64>,
+    found_entries: Arc<Mutex<Vec<UrlEntry>>>,
+}
+
+enum LinkCheckOutcome {
+    Success(reqwest::StatusCode),
+    Redirect {
+        status: reqwest::StatusCode,
+        location: String,
+    },
+    HttpError(reqwest::StatusCode),
+    Transport(String),
+}
+
+// Falls back to GET on a 405; a final URL that differs from the requested
+// one (the client follows redirects) is reported as a redirect.
+async fn check_link(client: &reqwest::Client, url: &str) -> LinkCheckOutcome {
+    let response = match client.head(url).send().await {
+        Ok(resp) if resp.status() == reqwest::StatusCode::METHOD_NOT_ALLOWED => {
+            match client.get(url).send().await {
+                Ok(resp) => resp,
+                Err(e) => return LinkCheckOutcome::Transport(e.to_string()),
+            }
+        }
+        Ok(resp) => resp,
+        Err(e) => return LinkCheckOutcome::Transport(e.to_string()),
+    };
+
+    let status = response.status();
+    let final_url = response.url().as_str();
+    if final_url != url {
+        LinkCheckOutcome::Redirect {
+            status,
+            location: final_url.to_string(),
+        }
+    } else if status.is_success() {
+        LinkCheckOutcome::Success(status)
+    } else {
+        LinkCheckOutcome::HttpError(status)
+    }
+}
+
+// Already-visited URLs and URLs beyond max_depth are skipped to guard
+// against self-referential loops and unbounded recursion.
+fn crawl(crawler: Crawler, url: String, depth: usize) -> BoxFuture<'static, Result<HashMap<String, usize>>> {
+    async move {
+        {
+            let mut visited = crawler.visited.lock().await;
+            if !visited.insert(url.clone()) {
+                if crawler.debug {
+                    eprintln!("[DEBUG] Skipping already-visited sitemap: {}", url);
+                }
+                return Ok(HashMap::new());
+            }
+        }
+        if depth > crawler.max_depth {
+            if crawler.debug {
+                eprintln!("[DEBUG] Max depth {} reached at {}, skipping", crawler.max_depth, url);
+            }
+            return Ok(HashMap::new());
+        }
+
+        if let Some(limiter) = &crawler.rate_limiter {
+            limiter.acquire().await;
+        }
+        let content = {
+            let _permit = crawler.semaphore.acquire().await.expect("semaphore closed");
+            if crawler.debug {
+                eprintln!("[DEBUG] Processing sitemap: {}", url);
+            }
+            fetch_url(&crawler.client, &url, crawler.debug).await?
+        };
+
+        match detect_root(&content)? {
+            RootKind::SitemapIndex => {
+                let children = extract_sitemaps(&content, crawler.debug)?;
+                let mut tasks = Vec::with_capacity(children.len());
+                for child in children {
+                    let crawler = crawler.clone();
+                    tasks.push(tokio::spawn(crawl(crawler, child, depth + 1)));
+                }
+                let mut results = HashMap::new();
+                for task in tasks {
+                    results.extend(task.await.context("sitemap task panicked")??);
+                }
+                Ok(results)
+            }
+            RootKind::UrlSet => {
+                let entries = extract_url_entries(&content, crawler.debug)?;
+                let count = entries.len();
+                crawler.found_entries.lock().await.extend(entries);
+                if crawler.debug {
+                    eprintln!(
+                        "[DEBUG] Sitemap {} has {} URLs (content size: {} bytes)",
+                        url,
+                        count,
+                        content.len()
+                    );
+                }
+                crawler.completed.fetch_add(1, Ordering::SeqCst);
+                crawler.pb.set_position(crawler.completed.load(Ordering::SeqCst));
+                let mut map = HashMap::new();
+                map.insert(url, count);
+                Ok(map)
+            }
+        }
     }
-    let main_sitemap_url = &args[1];
-    let debug = args.len() == 3 && args[2] == "--debug";
+    .boxed()
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let args = parse_args()?;
+    let debug = args.debug;
+    let machine = args.output.is_machine();
 
-    let client = reqwest::blocking::Client::builder()
+    let client = reqwest::Client::builder()
         .cookie_store(true)
         .timeout(Duration::from_secs(15))
+        .redirect(reqwest::redirect::Policy::limited(10))
+        .gzip(false) // we decompress manually so we can also handle `.xml.gz` URLs without the header
         .build()
         .context("Failed to build HTTP client")?;
-    println!("🌐 Fetching main sitemap from {}", main_sitemap_url);
-    let content = fetch_url(&client, main_sitemap_url, debug)?;
-    
-    let mut sitemap_urls = extract_sitemaps(&content, debug)?;
-    if sitemap_urls.is_empty() {
-        // If no sub-sitemaps found, treat the main URL as a regular sitemap
-        sitemap_urls.push(main_sitemap_url.to_string());
-    }
-
-    let pb = ProgressBar::new(sitemap_urls.len() as u64);
-    pb.set_style(ProgressStyle::default_bar()
-        .template("{spinner:.green} [{bar:40.cyan/blue}] {pos}/{len} sitemaps ({eta})")?
-        .progress_chars("#>-"));
-
-    let mut url_counts = HashMap::new();
-    
-    for sitemap_url in sitemap_urls {
-        if debug {
-            println!("[DEBUG] Processing sitemap: {}", sitemap_url);
-        }
-        let content = fetch_url(&client, &sitemap_url, debug)?;
-        let count = count_urls(&content, debug)?;
-        if debug {
-            println!(
-                "[DEBUG] Sitemap {} has {} URLs (content size: {} bytes)",
-                sitemap_url,
-                count,
-                content.len()
-            );
-        }
-        url_counts.insert(sitemap_url.clone(), count);
-        pb.inc(1);
-    }
+    chatter!(machine, "🌐 Fetching main sitemap from {}", args.main_sitemap_url);
+
+    let pb = ProgressBar::new_spinner();
+    pb.enable_steady_tick(Duration::from_millis(100));
+    pb.set_style(ProgressStyle::default_spinner().template("{spinner:.green} {pos} sitemaps processed")?);
+
+    let semaphore = Arc::new(Semaphore::new(args.concurrency));
+    let rate_limiter = args.rate_limit.map(|r| Arc::new(RateLimiter::new(r)));
+    let found_entries = Arc::new(Mutex::new(Vec::new()));
+
+    let crawler = Crawler {
+        client: client.clone(),
+        semaphore: semaphore.clone(),
+        rate_limiter: rate_limiter.clone(),
+        visited: Arc::new(Mutex::new(HashSet::new())),
+        max_depth: args.max_depth,
+        debug,
+        pb: pb.clone(),
+        completed: Arc::new(AtomicU64::new(0)),
+        found_entries: found_entries.clone(),
+    };
+
+    let url_counts = crawl(crawler, args.main_sitemap_url.clone(), 0).await?;
 
     pb.finish_with_message("Done!");
-    
-    println!("\n📊 Results:");
+
+    chatter!(machine, "\n📊 Results:");
     let total_urls: usize = url_counts.values().sum();
-    for (url, count) in url_counts {
-        println!("  {} - {} URLs", url, count);
+    for (url, count) in &url_counts {
+        chatter!(machine, "  {} - {} URLs", url, count);
+    }
+    chatter!(machine, "\n📈 Total URLs found: {}", total_urls);
+
+    let entries = std::mem::take(&mut *found_entries.lock().await);
+    print_metadata_summary(&entries, machine);
+
+    if args.check_links {
+        let urls = entries.iter().map(|e| e.loc.clone()).collect();
+        check_all_links(&client, &semaphore, &rate_limiter, urls, machine).await?;
+    }
+
+    match args.output {
+        OutputFormat::Text => {}
+        OutputFormat::Json => print_json_report(&url_counts, total_urls, &entries)?,
+        OutputFormat::Csv => print_csv_report(&entries)?,
+    }
+
+    Ok(())
+}
+
+// Goes through f32's own round-trip string form so e.g. 0.8 stays 0.8
+// instead of widening to f64 first and printing 0.800000011920929.
+fn priority_to_json_number(priority: f32) -> serde_json::Number {
+    priority
+        .to_string()
+        .parse()
+        .expect("f32::to_string() is always a valid JSON number")
+}
+
+fn print_json_report(url_counts: &HashMap<String, usize>, total_urls: usize, entries: &[UrlEntry]) -> Result<()> {
+    let urls: Vec<serde_json::Value> = entries
+        .iter()
+        .map(|entry| {
+            serde_json::json!({
+                "loc": entry.loc,
+                "lastmod": entry.lastmod.map(|dt| dt.to_rfc3339()),
+                "changefreq": entry.changefreq.map(|f| f.to_string()),
+                "priority": entry.priority.map(priority_to_json_number),
+            })
+        })
+        .collect();
+
+    let report = serde_json::json!({
+        "sitemap_counts": url_counts,
+        "total_urls": total_urls,
+        "urls": urls,
+    });
+
+    println!("{}", serde_json::to_string_pretty(&report)?);
+    Ok(())
+}
+
+fn print_csv_report(entries: &[UrlEntry]) -> Result<()> {
+    let mut writer = csv::Writer::from_writer(io::stdout());
+    writer.write_record(["loc", "lastmod", "changefreq", "priority"])?;
+    for entry in entries {
+        let lastmod = entry.lastmod.map(|dt| dt.to_rfc3339()).unwrap_or_default();
+        let changefreq = entry.changefreq.map(|f| f.to_string()).unwrap_or_default();
+        let priority = entry.priority.map(|p| p.to_string()).unwrap_or_default();
+        writer.write_record([entry.loc.as_str(), lastmod.as_str(), changefreq.as_str(), priority.as_str()])?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+async fn check_all_links(
+    client: &reqwest::Client,
+    semaphore: &Arc<Semaphore>,
+    rate_limiter: &Option<Arc<RateLimiter>>,
+    urls: Vec<String>,
+    machine: bool,
+) -> Result<()> {
+    let pb = ProgressBar::new(urls.len() as u64);
+    pb.set_style(
+        ProgressStyle::default_bar()
+            .template("{spinner:.green} [{bar:40.cyan/blue}] {pos}/{len} links checked ({eta})")?
+            .progress_chars("#>-"),
+    );
+
+    let mut tasks = Vec::with_capacity(urls.len());
+    for url in urls {
+        let client = client.clone();
+        let semaphore = semaphore.clone();
+        let rate_limiter = rate_limiter.clone();
+        let pb = pb.clone();
+        tasks.push(tokio::spawn(async move {
+            if let Some(limiter) = &rate_limiter {
+                limiter.acquire().await;
+            }
+            let _permit = semaphore.acquire().await.expect("semaphore closed");
+            let outcome = check_link(&client, &url).await;
+            pb.inc(1);
+            (url, outcome)
+        }));
+    }
+
+    let mut success_by_status: HashMap<reqwest::StatusCode, usize> = HashMap::new();
+    let mut redirects = Vec::new();
+    let mut errors = Vec::new();
+    let mut transport_errors = Vec::new();
+    for task in tasks {
+        let (url, outcome) = task.await.context("link check task panicked")?;
+        match outcome {
+            LinkCheckOutcome::Success(status) => *success_by_status.entry(status).or_insert(0) += 1,
+            LinkCheckOutcome::Redirect { status, location } => redirects.push((url, status, location)),
+            LinkCheckOutcome::HttpError(status) => errors.push((url, status)),
+            LinkCheckOutcome::Transport(err) => transport_errors.push((url, err)),
+        }
+    }
+
+    pb.finish_and_clear();
+
+    let success_count: usize = success_by_status.values().sum();
+    chatter!(machine, "\n🔗 Link check results:");
+    chatter!(machine, "  ✅ {} OK", success_count);
+    if success_by_status.len() > 1 {
+        let mut by_status: Vec<_> = success_by_status.into_iter().collect();
+        by_status.sort_by_key(|(status, _)| status.as_u16());
+        for (status, count) in by_status {
+            chatter!(machine, "    {} x {}", count, status);
+        }
+    }
+    if !redirects.is_empty() {
+        chatter!(machine, "  🔀 {} redirected:", redirects.len());
+        for (url, status, location) in &redirects {
+            chatter!(machine, "    {} ({}) -> {}", url, status, location);
+        }
+    }
+    if !errors.is_empty() {
+        chatter!(machine, "  ❌ {} returned an error status:", errors.len());
+        for (url, status) in &errors {
+            chatter!(machine, "    {} - {}", url, status);
+        }
+    }
+    if !transport_errors.is_empty() {
+        chatter!(machine, "  💥 {} failed outright:", transport_errors.len());
+        for (url, err) in &transport_errors {
+            chatter!(machine, "    {} - {}", url, err);
+        }
     }
-    println!("\n📈 Total URLs found: {}", total_urls);
 
     Ok(())
 }
 
-fn fetch_url(_client: &reqwest::blocking::Client, url: &str, debug: bool) -> Result<String> {
-    use std::process::Command;
-    
-    // Build the curl command. We use "-s" for silent mode.
-    let output = Command::new("curl")
-        .arg("-s")
-        .arg("-L")
-        .arg("-A")
-        .arg("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/115.0.0.0 Safari/537.36")
-        .arg(url)
-        .output()
-        .context("Failed to execute curl")?;
-        
-    if !output.status.success() {
-        return Err(anyhow::anyhow!("curl command failed with status: {:?}", output.status));
-    }
-    
-    let content = String::from_utf8(output.stdout)
-        .context("Curl output was not valid UTF-8")?;
-    
-    // Optionally, add debug info:
-    if debug { println!("[DEBUG] Curl fetched content length: {}", content.len()); }
+async fn fetch_url(client: &reqwest::Client, url: &str, debug: bool) -> Result<String> {
+    let response = client
+        .get(url)
+        .header(
+            "User-Agent",
+            "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/115.0.0.0 Safari/537.36",
+        )
+        .send()
+        .await
+        .context("Failed to fetch URL")?;
+
+    let is_gzipped = response
+        .headers()
+        .get(reqwest::header::CONTENT_ENCODING)
+        .and_then(|v| v.to_str().ok())
+        .map(|v| v.eq_ignore_ascii_case("gzip"))
+        .unwrap_or(false)
+        || url.ends_with(".gz");
+
+    let bytes = response
+        .bytes()
+        .await
+        .context("Failed to read response body")?;
+
+    let content = if is_gzipped {
+        let mut decoder = GzDecoder::new(&bytes[..]);
+        let mut decompressed = String::new();
+        decoder
+            .read_to_string(&mut decompressed)
+            .context("Failed to decompress gzip response")?;
+        decompressed
+    } else {
+        String::from_utf8(bytes.to_vec()).context("Response body was not valid UTF-8")?
+    };
+
     if debug {
-        println!(
-            "[DEBUG] Curl fetched content snippet: {}",
+        eprintln!("[DEBUG] Fetched content length: {}", content.len());
+    }
+    if debug {
+        eprintln!(
+            "[DEBUG] Fetched content snippet: {}",
             &content[..std::cmp::min(500, content.len())]
         );
     }
-    if debug { println!("[DEBUG] Fetched sitemap content. Proceeding to clean XML and parse document..."); }
-    io::stdout().flush().unwrap();
-    
+    if debug {
+        eprintln!("[DEBUG] Fetched sitemap content. Proceeding to clean XML and parse document...");
+    }
+
     Ok(clean_xml_content(&content))
 }
 
 fn extract_sitemaps(content: &str, debug: bool) -> Result<Vec<String>> {
-    if debug { println!("[DEBUG] Starting sitemap extraction from main sitemap content..."); }
-    io::stdout().flush().unwrap();
+    if debug { eprintln!("[DEBUG] Starting sitemap extraction from sitemap index content..."); }
     let mut buf = Vec::new();
 
-    let mut count: usize = 0;
-    let pb = ProgressBar::new_spinner();
-    pb.enable_steady_tick(Duration::from_millis(100));
-    pb.set_style(
-        ProgressStyle::default_spinner()
-            .template("{spinner:.green} {msg}")
-            .unwrap()
-    );
-    pb.set_message("Counting URLs: 0");
-
     let mut reader = Reader::from_str(content);
     reader.trim_text(true);
     let mut sitemaps = Vec::new();
@@ -147,8 +749,6 @@ fn extract_sitemaps(content: &str, debug: bool) -> Result<Vec<String>> {
                     },
                     Event::Text(e) if in_loc => {
                         sitemaps.push(e.unescape()?.into_owned());
-                        count += 1;
-                        pb.set_message(format!("Counting URLs: {}", count));
                         in_loc = false;
                     },
                     _ => (),
@@ -158,42 +758,7 @@ fn extract_sitemaps(content: &str, debug: bool) -> Result<Vec<String>> {
         }
         buf.clear();
     }
-    pb.finish_and_clear();
-    if debug { println!("[DEBUG] Completed sitemap extraction. Found {} sitemaps.", sitemaps.len()); }
-    io::stdout().flush().unwrap();
+    if debug { eprintln!("[DEBUG] Completed sitemap extraction. Found {} sitemaps.", sitemaps.len()); }
     Ok(sitemaps)
 }
 
-fn count_urls(content: &str, debug: bool) -> Result<usize> {
-    let mut reader = Reader::from_str(content);
-    let mut buf = Vec::new();
-    let mut count = 0;
-    let mut in_url = false;
-    let mut in_loc = false;
-
-    loop {
-        match reader.read_event_into(&mut buf) {
-            Ok(Event::Start(ref e)) if e.name().as_ref() == b"url" => {
-                in_url = true;
-            }
-            Ok(Event::End(ref e)) if e.name().as_ref() == b"url" => {
-                in_url = false;
-            }
-            Ok(Event::Start(ref e)) if in_url && e.name().as_ref() == b"loc" => {
-                in_loc = true;
-            }
-            Ok(Event::Text(e)) if in_loc => {
-                let _url_text = e.unescape()?.into_owned();
-                count += 1;
-                in_loc = false;
-            }
-            Ok(Event::Eof) => break,
-            Err(e) => return Err(e.into()),
-            _ => (),
-        }
-        buf.clear();
-    }
-    if debug { println!("[DEBUG] Finished URL count. Found {} URLs.", count); }
-    io::stdout().flush().unwrap();
-    Ok(count)
-}